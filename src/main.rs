@@ -1,13 +1,122 @@
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand};
-use serde::{Deserialize, Serialize};
+use clap::{Parser, Subcommand, ValueEnum};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crossterm::{cursor, execute, style, terminal};
+use rand::RngCore;
+use serde::{Deserialize, Deserializer, Serialize};
+use ssh_key::{HashAlg, PrivateKey};
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-type Profiles = HashMap<String, HashMap<String, String>>;
+type Profiles = HashMap<String, HashMap<String, HostEntry>>;
+
+/// Which credential a host entry manages, and therefore which dotfile its
+/// gakun-delimited block lives in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+#[value(rename_all = "lowercase")]
+enum Platform {
+    /// SSH identity written into `~/.ssh/config`.
+    #[default]
+    Ssh,
+    /// GPG signing key written into `~/.gitconfig`.
+    Gpg,
+}
+
+/// Everything gakun knows about a single host alias. Only `key` is required;
+/// `user`, `port` and `hostname` map onto optional `~/.ssh/config`
+/// directives, the rest is bookkeeping kept by gakun itself.
+#[derive(Debug, Clone, Default, Serialize)]
+struct HostEntry {
+    key: String,
+    #[serde(default)]
+    platform: Platform,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    /// Rotate the key once it is older than this many days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rotate_after_days: Option<u32>,
+    /// Unix seconds of the last rotation (or the initial `add`); the anchor
+    /// from which key age is measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_used: Option<i64>,
+}
+
+impl<'de> Deserialize<'de> for HostEntry {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Accept both the legacy bare-string form (`host -> key path`) and the
+        // richer struct form so old configs keep loading.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Legacy(String),
+            Full {
+                key: String,
+                #[serde(default)]
+                platform: Platform,
+                #[serde(default)]
+                user: Option<String>,
+                #[serde(default)]
+                port: Option<u16>,
+                #[serde(default)]
+                hostname: Option<String>,
+                #[serde(default)]
+                algorithm: Option<String>,
+                #[serde(default)]
+                fingerprint: Option<String>,
+                #[serde(default)]
+                rotate_after_days: Option<u32>,
+                #[serde(default)]
+                last_used: Option<i64>,
+            },
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Legacy(key) => HostEntry {
+                key,
+                ..Default::default()
+            },
+            Raw::Full {
+                key,
+                platform,
+                user,
+                port,
+                hostname,
+                algorithm,
+                fingerprint,
+                rotate_after_days,
+                last_used,
+            } => HostEntry {
+                key,
+                platform,
+                user,
+                port,
+                hostname,
+                algorithm,
+                fingerprint,
+                rotate_after_days,
+                last_used,
+            },
+        })
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Config {
@@ -32,18 +141,35 @@ struct Gakun {
     config: Config,
     config_path: PathBuf,
     ssh_config_path: PathBuf,
+    gitconfig_path: PathBuf,
+    /// Whether the on-disk config is sealed with AES-256-GCM.
+    encrypted: bool,
+    /// Passphrase kept in memory so `save_config` can re-seal after a load.
+    passphrase: Option<String>,
 }
 
+/// Magic prefix marking an encrypted config. Layout of the file is:
+/// `MAGIC | version(1) | rounds(4, BE) | salt(16) | nonce(12) | ciphertext`.
+const ENC_MAGIC: &[u8] = b"GAKUNENC";
+const ENC_VERSION: u8 = 1;
+const ENC_SALT_LEN: usize = 16;
+const ENC_NONCE_LEN: usize = 12;
+const BCRYPT_ROUNDS: u32 = 16;
+
 impl Gakun {
     fn new() -> Result<Self> {
         let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
         let config_path = home.join(".config/gakun/config.json");
         let ssh_config_path = home.join(".ssh/config");
+        let gitconfig_path = home.join(".gitconfig");
 
         let mut gakun = Gakun {
             config: Config::default(),
             config_path,
             ssh_config_path,
+            gitconfig_path,
+            encrypted: false,
+            passphrase: None,
         };
 
         gakun.load_config()?;
@@ -58,9 +184,19 @@ impl Gakun {
         }
 
         // Try to read existing config
-        match fs::read_to_string(&self.config_path) {
-            Ok(data) => {
-                self.config = serde_json::from_str(&data)
+        match fs::read(&self.config_path) {
+            Ok(bytes) => {
+                let json = if bytes.starts_with(ENC_MAGIC) {
+                    let passphrase = prompt_passphrase("Passphrase to unlock gakun config: ")?;
+                    let plain = decrypt_blob(&bytes, &passphrase)?;
+                    self.encrypted = true;
+                    self.passphrase = Some(passphrase);
+                    String::from_utf8(plain).context("Decrypted config is not valid UTF-8")?
+                } else {
+                    String::from_utf8(bytes).context("Config file is not valid UTF-8")?
+                };
+
+                self.config = serde_json::from_str(&json)
                     .context("Failed to parse config file")?;
             }
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
@@ -82,47 +218,286 @@ impl Gakun {
         let json = serde_json::to_string(&self.config)
             .context("Failed to serialize config")?;
 
-        fs::write(&self.config_path, json)
+        let bytes = if self.encrypted {
+            let passphrase = self
+                .passphrase
+                .as_ref()
+                .ok_or_else(|| anyhow!("Encrypted config has no passphrase in memory"))?;
+            encrypt_blob(json.as_bytes(), passphrase)?
+        } else {
+            json.into_bytes()
+        };
+
+        fs::write(&self.config_path, bytes)
             .context("Failed to write config file")?;
 
         Ok(())
     }
 
-    fn add(&mut self, profile: &str, host: &str, key: &str) -> Result<()> {
-        // Validate that the key file exists
-        fs::metadata(key)
-            .with_context(|| format!("SSH key path is not valid: {}", key))?;
+    /// Turn on encryption at rest, prompting for (and confirming) a new
+    /// passphrase, then re-write the config sealed.
+    fn encrypt(&mut self) -> Result<()> {
+        if self.encrypted {
+            println!("Config is already encrypted.");
+            return Ok(());
+        }
+
+        let passphrase = prompt_passphrase("New passphrase: ")?;
+        let confirm = prompt_passphrase("Confirm passphrase: ")?;
+        if passphrase != confirm {
+            return Err(anyhow!("Passphrases do not match"));
+        }
+        if passphrase.is_empty() {
+            return Err(anyhow!("Passphrase must not be empty"));
+        }
+
+        self.encrypted = true;
+        self.passphrase = Some(passphrase);
+        self.save_config()?;
+
+        println!("Config is now encrypted ✓");
+        Ok(())
+    }
+
+    /// Turn off encryption, writing the config back as plaintext JSON.
+    fn decrypt(&mut self) -> Result<()> {
+        if !self.encrypted {
+            println!("Config is not encrypted.");
+            return Ok(());
+        }
+
+        self.encrypted = false;
+        self.passphrase = None;
+        self.save_config()?;
+
+        println!("Config is now stored as plaintext ✓");
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        profile: &str,
+        host: &str,
+        key: &str,
+        platform: Platform,
+        user: Option<String>,
+        port: Option<u16>,
+        hostname: Option<String>,
+        rotate_after_days: Option<u32>,
+    ) -> Result<()> {
+        // For SSH, parse the key so we reject typo'd, public-only or otherwise
+        // invalid paths before they reach ~/.ssh/config, and record its
+        // algorithm and fingerprint. GPG key ids are stored as given.
+        let (algorithm, fingerprint) = match platform {
+            Platform::Ssh => inspect_key(key)?,
+            Platform::Gpg => (None, None),
+        };
+
+        let entry = HostEntry {
+            key: key.to_string(),
+            platform,
+            user,
+            port,
+            hostname,
+            algorithm,
+            fingerprint,
+            rotate_after_days,
+            last_used: Some(now_secs()),
+        };
 
         // Add to config
         self.config.profiles
             .entry(profile.to_string())
             .or_insert_with(HashMap::new)
-            .insert(host.to_string(), key.to_string());
+            .insert(host.to_string(), entry);
 
         self.save_config()?;
 
         Ok(())
     }
 
+    /// Collect `profile → host` combinations as `(profile, host)` pairs, sorted
+    /// so the picker shows a stable order. When `only` is set, restrict to that
+    /// profile.
+    fn combinations(&self, only: Option<&str>) -> Vec<(String, String)> {
+        let mut combos: Vec<(String, String)> = Vec::new();
+        for (profile, hosts) in &self.config.profiles {
+            if only.is_some_and(|p| p != profile) {
+                continue;
+            }
+            for host in hosts.keys() {
+                combos.push((profile.clone(), host.clone()));
+            }
+        }
+        combos.sort();
+        combos
+    }
+
+    /// Drop into an interactive fuzzy selector over `profile → host`
+    /// combinations and activate the chosen one, mirroring `use_profile`.
+    /// With `only` set, the picker is scoped to that profile.
+    fn pick_and_use(&mut self, only: Option<&str>) -> Result<()> {
+        if let Some(profile) = only {
+            if !self.config.profiles.contains_key(profile) {
+                return Err(anyhow!("No such profile: {}", profile));
+            }
+        }
+
+        let combos = self.combinations(only);
+        if combos.is_empty() {
+            return Err(anyhow!(
+                "No profiles configured yet. Add one with 'gakun add'."
+            ));
+        }
+
+        let labels: Vec<String> = combos
+            .iter()
+            .map(|(profile, host)| format!("{}/{}", profile, host))
+            .collect();
+
+        match fuzzy_select("use", &labels)? {
+            Some(index) => {
+                let (profile, host) = &combos[index];
+                let (profile, host) = (profile.clone(), host.clone());
+                self.use_profile(&profile, &host)
+            }
+            None => {
+                println!("Nothing selected.");
+                Ok(())
+            }
+        }
+    }
+
     fn use_profile(&mut self, profile: &str, host: &str) -> Result<()> {
-        let key = self.config.profiles
+        let entry = self.config.profiles
             .get(profile)
             .and_then(|hosts| hosts.get(host))
             .ok_or_else(|| anyhow!(
                 "There is no such profile and host combination. Please type gakun ls to show your profiles and hosts."
             ))?;
 
-        let data = self.read_file_with_skip_section()?;
+        let entry = entry.clone();
+        match entry.platform {
+            Platform::Ssh => self.use_ssh(host, &entry)?,
+            Platform::Gpg => self.use_gpg(host, &entry)?,
+        }
+
+        Ok(())
+    }
 
-        let new_config = format!(
-            "###### gakun begin\nHost {}\n  Hostname {}\n  IdentityFile {}\n###### gakun end\n",
-            host, host, key
+    fn use_ssh(&self, host: &str, entry: &HostEntry) -> Result<()> {
+        let data = self.read_file_with_skip_section(&self.ssh_config_path)?;
+
+        let hostname = entry.hostname.as_deref().unwrap_or(host);
+        let mut block = format!(
+            "###### gakun begin\nHost {}\n  Hostname {}\n  IdentityFile {}\n",
+            host, hostname, entry.key
         );
+        if let Some(user) = &entry.user {
+            block.push_str(&format!("  User {}\n", user));
+        }
+        if let Some(port) = entry.port {
+            block.push_str(&format!("  Port {}\n", port));
+        }
+        block.push_str("###### gakun end\n");
 
-        fs::write(&self.ssh_config_path, format!("{}{}", new_config, data))
+        fs::write(&self.ssh_config_path, format!("{}{}", block, data))
             .context("Failed to write SSH config")?;
 
-        println!("Key {} is now active for {} ✓", key, host);
+        println!("Key {} is now active for {} ✓", entry.key, host);
+        if let Some(warning) = rotation_warning(entry) {
+            println!("{}", warning);
+        }
+
+        Ok(())
+    }
+
+    fn use_gpg(&self, host: &str, entry: &HostEntry) -> Result<()> {
+        let data = self.read_file_with_skip_section(&self.gitconfig_path)?;
+
+        let block = format!(
+            "###### gakun begin\n[user]\n\tsigningkey = {}\n[gpg]\n\tformat = openpgp\n###### gakun end\n",
+            entry.key
+        );
+
+        fs::write(&self.gitconfig_path, format!("{}{}", block, data))
+            .context("Failed to write git config")?;
+
+        println!("Signing key {} is now active for {} ✓", entry.key, host);
+        if let Some(warning) = rotation_warning(entry) {
+            println!("{}", warning);
+        }
+
+        Ok(())
+    }
+
+    /// Open a profile's host entries as TOML in `$EDITOR`, re-parse and
+    /// validate the result, and only replace the stored profile if it is
+    /// sound. A parse or validation failure leaves the config untouched.
+    fn edit(&mut self, profile: &str) -> Result<()> {
+        let hosts = self.config.profiles
+            .get(profile)
+            .ok_or_else(|| anyhow!("No such profile: {}", profile))?;
+
+        let toml_str = toml::to_string_pretty(hosts)
+            .context("Failed to serialize profile to TOML")?;
+
+        let mut path = std::env::temp_dir();
+        path.push(format!("gakun-{}.toml", profile));
+        fs::write(&path, &toml_str)
+            .context("Failed to write temp file for editing")?;
+
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        let status = std::process::Command::new(&editor)
+            .arg(&path)
+            .status()
+            .with_context(|| format!("Failed to launch editor: {}", editor))?;
+        if !status.success() {
+            return Err(anyhow!("Editor exited without saving"));
+        }
+
+        let edited = fs::read_to_string(&path)
+            .context("Failed to read edited file")?;
+        let mut parsed: HashMap<String, HostEntry> = toml::from_str(&edited)
+            .context("Edited profile is not valid TOML")?;
+
+        // Validate before committing so a mistyped edit never lands in the
+        // stored config, and re-derive the fingerprint so a changed key path
+        // never leaves a stale algorithm/fingerprint behind.
+        for (host, entry) in &mut parsed {
+            if entry.platform == Platform::Ssh {
+                let (algorithm, fingerprint) = inspect_key(&entry.key)
+                    .with_context(|| format!("Invalid key for {}", host))?;
+                entry.algorithm = algorithm;
+                entry.fingerprint = fingerprint;
+            }
+            if matches!(entry.port, Some(0)) {
+                return Err(anyhow!("Invalid port 0 for {}", host));
+            }
+        }
+
+        self.config.profiles.insert(profile.to_string(), parsed);
+        self.save_config()?;
+        let _ = fs::remove_file(&path);
+
+        println!("Profile {} updated ✓", profile);
+        Ok(())
+    }
+
+    /// Reset a host's rotation anchor after the user has swapped the
+    /// underlying key, so the stale-key reminder starts counting afresh.
+    fn rotate(&mut self, profile: &str, host: &str) -> Result<()> {
+        let entry = self.config.profiles
+            .get_mut(profile)
+            .and_then(|hosts| hosts.get_mut(host))
+            .ok_or_else(|| anyhow!(
+                "There is no such profile and host combination. Please type gakun ls to show your profiles and hosts."
+            ))?;
+
+        entry.last_used = Some(now_secs());
+        self.save_config()?;
+
+        println!("Rotation timer reset for {}/{} ✓", profile, host);
 
         Ok(())
     }
@@ -130,20 +505,61 @@ impl Gakun {
     fn list(&self) -> Result<()> {
         for (profile, hosts) in &self.config.profiles {
             println!("\n{}:", profile);
-            for (host, key) in hosts {
-                println!("   {} → {}", host, key);
+            for (host, entry) in hosts {
+                self.print_entry(host, entry);
             }
         }
         Ok(())
     }
 
-    fn read_file_with_skip_section(&self) -> Result<String> {
-        let file = File::open(&self.ssh_config_path)
+    /// Print a single host entry the same way `list` does.
+    fn print_entry(&self, host: &str, entry: &HostEntry) {
+        println!("   {} → {}", host, entry.key);
+        if let (Some(algorithm), Some(fingerprint)) = (&entry.algorithm, &entry.fingerprint) {
+            println!("      {} {}", algorithm, fingerprint);
+        }
+        if let Some(warning) = rotation_warning(entry) {
+            println!("      {}", warning);
+        }
+    }
+
+    /// Interactive counterpart to `list`: fuzzy-search all `profile → host`
+    /// combinations and print the details of the chosen one.
+    fn browse(&self) -> Result<()> {
+        let combos = self.combinations(None);
+        if combos.is_empty() {
+            println!("No profiles configured yet. Add one with 'gakun add'.");
+            return Ok(());
+        }
+
+        let labels: Vec<String> = combos
+            .iter()
+            .map(|(profile, host)| format!("{}/{}", profile, host))
+            .collect();
+
+        if let Some(index) = fuzzy_select("ls", &labels)? {
+            let (profile, host) = &combos[index];
+            if let Some(entry) = self
+                .config
+                .profiles
+                .get(profile)
+                .and_then(|hosts| hosts.get(host))
+            {
+                println!("\n{}:", profile);
+                self.print_entry(host, entry);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_file_with_skip_section(&self, path: &Path) -> Result<String> {
+        let file = File::open(path)
             .or_else(|_| {
                 // If file doesn't exist, create it
-                File::create(&self.ssh_config_path)
+                File::create(path)
             })
-            .context("Failed to open SSH config file")?;
+            .context("Failed to open config file")?;
 
         let reader = BufReader::new(file);
         let mut lines = Vec::new();
@@ -172,19 +588,307 @@ impl Gakun {
     }
 
     fn detach(&self) -> Result<()> {
-        // Read the current SSH config and remove gakun-managed section
-        let data = self.read_file_with_skip_section()?;
+        // Strip the gakun-managed section from every file a platform owns.
+        for path in [&self.ssh_config_path, &self.gitconfig_path] {
+            if !path.exists() {
+                continue;
+            }
 
-        // Write back the cleaned config
-        fs::write(&self.ssh_config_path, data)
-            .context("Failed to write SSH config")?;
+            let data = self.read_file_with_skip_section(path)?;
+            fs::write(path, data)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
 
-        println!("Gakun section removed from {} ✓", self.ssh_config_path.display());
+            println!("Gakun section removed from {} ✓", path.display());
+        }
 
         Ok(())
     }
 }
 
+/// Current Unix time in seconds.
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// Build the `⚠ Nd old, rotate` warning for an entry past its threshold, or
+/// `None` if it has no threshold, no anchor, or is still within its window.
+fn rotation_warning(entry: &HostEntry) -> Option<String> {
+    let threshold = entry.rotate_after_days?;
+    let last_used = entry.last_used?;
+    let age_days = (now_secs() - last_used) / 86_400;
+    if age_days >= threshold as i64 {
+        // Yellow, reset afterwards.
+        Some(format!("\x1b[33m⚠ {}d old, rotate\x1b[0m", age_days))
+    } else {
+        None
+    }
+}
+
+/// Prompt for a passphrase on the controlling terminal without echoing it.
+fn prompt_passphrase(prompt: &str) -> Result<String> {
+    rpassword::prompt_password(prompt).context("Failed to read passphrase")
+}
+
+/// Derive a 256-bit key from `passphrase` with bcrypt-pbkdf over `salt`.
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    bcrypt_pbkdf::bcrypt_pbkdf(passphrase.as_bytes(), salt, rounds, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Seal `plaintext` with AES-256-GCM under a freshly salted key, returning the
+/// full header + ciphertext blob described by [`ENC_MAGIC`].
+fn encrypt_blob(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; ENC_SALT_LEN];
+    let mut nonce_bytes = [0u8; ENC_NONCE_LEN];
+    let mut rng = rand::thread_rng();
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt, BCRYPT_ROUNDS)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Bad key: {}", e))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+
+    let mut out = Vec::with_capacity(
+        ENC_MAGIC.len() + 1 + 4 + ENC_SALT_LEN + ENC_NONCE_LEN + ciphertext.len(),
+    );
+    out.extend_from_slice(ENC_MAGIC);
+    out.push(ENC_VERSION);
+    out.extend_from_slice(&BCRYPT_ROUNDS.to_be_bytes());
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Inverse of [`encrypt_blob`]. Fails cleanly on a truncated header, an
+/// unsupported version, or a bad authentication tag.
+fn decrypt_blob(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let header_len = ENC_MAGIC.len() + 1 + 4 + ENC_SALT_LEN + ENC_NONCE_LEN;
+    if data.len() < header_len {
+        return Err(anyhow!("Encrypted config is truncated"));
+    }
+
+    let mut offset = ENC_MAGIC.len();
+    let version = data[offset];
+    offset += 1;
+    if version != ENC_VERSION {
+        return Err(anyhow!("Unsupported encrypted config version {}", version));
+    }
+
+    let rounds = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+    let salt = &data[offset..offset + ENC_SALT_LEN];
+    offset += ENC_SALT_LEN;
+    let nonce_bytes = &data[offset..offset + ENC_NONCE_LEN];
+    offset += ENC_NONCE_LEN;
+    let ciphertext = &data[offset..];
+
+    let key = derive_key(passphrase, salt, rounds)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Bad key: {}", e))?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| anyhow!("Incorrect passphrase or corrupted config"))
+}
+
+/// Validate the private key at `path` and, when possible, return its algorithm
+/// name (e.g. `ssh-ed25519`) and SHA256 fingerprint.
+///
+/// OpenSSH-format keys are parsed with `ssh-key` so we can fingerprint them.
+/// Traditional PEM/PKCS#1 identities (the classic `-----BEGIN RSA PRIVATE
+/// KEY-----` `id_rsa`) are accepted too but return `None` for algorithm and
+/// fingerprint, since `ssh-key` does not decode them. A missing path, a
+/// public key, or anything that is not a private key is rejected.
+fn inspect_key(path: &str) -> Result<(Option<String>, Option<String>)> {
+    if let Ok(key) = PrivateKey::read_openssh_file(Path::new(path)) {
+        let algorithm = key.algorithm().to_string();
+        let fingerprint = key.fingerprint(HashAlg::Sha256).to_string();
+        return Ok((Some(algorithm), Some(fingerprint)));
+    }
+
+    // Fall back to accepting a traditional PEM private key by inspecting its
+    // contents, matching the baseline behaviour for keys users already rely on.
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("SSH key path is not valid: {}", path))?;
+    if data.contains("PRIVATE KEY") {
+        Ok((None, None))
+    } else {
+        Err(anyhow!(
+            "{} is not a valid SSH private key (expected OpenSSH or PEM format)",
+            path
+        ))
+    }
+}
+
+/// Fuzzy subsequence match of `query` against `candidate`, case-insensitive.
+///
+/// Returns `None` when not every query character can be matched in order.
+/// Otherwise returns the score and the byte indices of `candidate` that were
+/// matched, so the caller can highlight them. Higher scores are better:
+/// consecutive runs earn a contiguity bonus, matches at the start or right
+/// after a separator (`/`, `.`, `-`, space) are rewarded, and gaps between
+/// matched characters are penalized.
+fn fuzzy_match(query: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let cand: Vec<(usize, char)> = candidate.char_indices().collect();
+    let query: Vec<char> = query.chars().collect();
+
+    let mut score = 0;
+    let mut matched = Vec::with_capacity(query.len());
+    let mut qi = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for (pos, (byte_idx, ch)) in cand.iter().enumerate() {
+        if qi >= query.len() {
+            break;
+        }
+
+        if ch.to_ascii_lowercase() == query[qi].to_ascii_lowercase() {
+            score += 1;
+
+            let after_separator = pos
+                .checked_sub(1)
+                .map(|p| matches!(cand[p].1, '/' | '.' | '-' | ' '))
+                .unwrap_or(false);
+            if pos == 0 || after_separator {
+                score += 8;
+            }
+
+            match prev_match {
+                Some(prev) if prev + 1 == pos => score += 5, // contiguous run
+                Some(prev) => score -= (pos - prev - 1) as i32, // gap penalty
+                None => {}
+            }
+
+            matched.push(*byte_idx);
+            prev_match = Some(pos);
+            qi += 1;
+        }
+    }
+
+    if qi == query.len() {
+        Some((score, matched))
+    } else {
+        None
+    }
+}
+
+/// Render a live-filtered list of `labels` and let the user pick one with a
+/// fuzzy query. `prompt` labels the query line (e.g. `use` or `ls`). Returns
+/// the index into `labels`, or `None` if cancelled.
+fn fuzzy_select(prompt: &str, labels: &[String]) -> Result<Option<usize>> {
+    let mut stdout = io::stdout();
+    enable_raw_mode().context("Failed to enter raw mode")?;
+    let result = run_selector(&mut stdout, prompt, labels);
+    disable_raw_mode().ok();
+    execute!(stdout, style::ResetColor).ok();
+    result
+}
+
+fn run_selector(stdout: &mut io::Stdout, prompt: &str, labels: &[String]) -> Result<Option<usize>> {
+    let mut query = String::new();
+    let mut cursor_row = 0usize;
+
+    loop {
+        // Rank candidates by descending score, keeping only the ones that match.
+        let mut ranked: Vec<(usize, i32, Vec<usize>)> = labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| {
+                fuzzy_match(&query, label).map(|(score, matched)| (i, score, matched))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        if cursor_row >= ranked.len() {
+            cursor_row = ranked.len().saturating_sub(1);
+        }
+
+        draw(stdout, prompt, &query, labels, &ranked, cursor_row)?;
+
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None);
+                }
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => {
+                    return Ok(ranked.get(cursor_row).map(|r| r.0));
+                }
+                KeyCode::Up => cursor_row = cursor_row.saturating_sub(1),
+                KeyCode::Down => {
+                    if cursor_row + 1 < ranked.len() {
+                        cursor_row += 1;
+                    }
+                }
+                KeyCode::Backspace => {
+                    query.pop();
+                    cursor_row = 0;
+                }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    cursor_row = 0;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+fn draw(
+    stdout: &mut io::Stdout,
+    prompt: &str,
+    query: &str,
+    labels: &[String],
+    ranked: &[(usize, i32, Vec<usize>)],
+    cursor_row: usize,
+) -> Result<()> {
+    execute!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::FromCursorDown)
+    )?;
+
+    write!(stdout, "{} > {}\r\n", prompt, query)?;
+
+    for (row, (idx, _score, matched)) in ranked.iter().enumerate() {
+        if row == cursor_row {
+            execute!(stdout, style::SetAttribute(style::Attribute::Reverse))?;
+            write!(stdout, "> ")?;
+        } else {
+            write!(stdout, "  ")?;
+        }
+
+        for (byte_idx, ch) in labels[*idx].char_indices() {
+            if matched.contains(&byte_idx) {
+                execute!(stdout, style::SetForegroundColor(style::Color::Green))?;
+                write!(stdout, "{}", ch)?;
+                execute!(stdout, style::SetForegroundColor(style::Color::Reset))?;
+            } else {
+                write!(stdout, "{}", ch)?;
+            }
+        }
+
+        if row == cursor_row {
+            execute!(stdout, style::SetAttribute(style::Attribute::Reset))?;
+        }
+        write!(stdout, "\r\n")?;
+    }
+
+    stdout.flush()?;
+    Ok(())
+}
+
 #[derive(Parser)]
 #[command(name = "gakun")]
 #[command(about = "SSH key manager", long_about = None)]
@@ -202,23 +906,59 @@ enum Commands {
         /// Host to configure
         #[arg(short = 'h', long)]
         host: String,
-        /// Path to SSH key
+        /// Path to SSH key (or GPG key id when --platform gpg)
         #[arg(short = 'k', long)]
         key: String,
+        /// Credential platform this entry manages
+        #[arg(long, value_enum, default_value_t = Platform::Ssh)]
+        platform: Platform,
+        /// Login user (emitted as `User` in ~/.ssh/config)
+        #[arg(short = 'u', long)]
+        user: Option<String>,
+        /// Port (emitted as `Port` in ~/.ssh/config)
+        #[arg(short = 'p', long)]
+        port: Option<u16>,
+        /// Real hostname if it differs from the alias (emitted as `Hostname`)
+        #[arg(long)]
+        hostname: Option<String>,
+        /// Warn once the key is older than this many days
+        #[arg(long)]
+        rotate_after_days: Option<u32>,
     },
-    #[command(about = "Use SSH key for certain host. Example: 'gakun use work -h gitlab.com'")]
+    #[command(about = "Use SSH key for certain host. Run without arguments to pick interactively. Example: 'gakun use work -h gitlab.com'")]
     Use {
         /// Profile name
-        profile: String,
+        profile: Option<String>,
         /// Host to configure
         #[arg(short = 'h', long)]
-        host: String,
+        host: Option<String>,
     },
     #[command(about = "List profiles")]
-    Ls,
-    #[command(about = "Detach gakun - remove gakun-managed section from ~/.ssh/config")]
+    Ls {
+        /// Interactively fuzzy-search and inspect a host entry
+        #[arg(short = 'i', long)]
+        interactive: bool,
+    },
+    #[command(about = "Detach gakun - remove the gakun-managed section from ~/.ssh/config and ~/.gitconfig")]
     #[command(alias = "d")]
     Detach,
+    #[command(about = "Edit a profile's hosts in $EDITOR. Example: 'gakun edit work'")]
+    Edit {
+        /// Profile name
+        profile: String,
+    },
+    #[command(about = "Reset the rotation timer for a host after swapping its key. Example: 'gakun rotate work -h gitlab.com'")]
+    Rotate {
+        /// Profile name
+        profile: String,
+        /// Host to reset
+        #[arg(short = 'h', long)]
+        host: String,
+    },
+    #[command(about = "Encrypt the config file at rest with a passphrase")]
+    Encrypt,
+    #[command(about = "Decrypt the config file back to plaintext JSON")]
+    Decrypt,
 }
 
 fn main() -> Result<()> {
@@ -226,19 +966,96 @@ fn main() -> Result<()> {
     let mut gakun = Gakun::new()?;
 
     match cli.command {
-        Commands::Add { profile, host, key } => {
-            gakun.add(&profile, &host, &key)?;
-        }
-        Commands::Use { profile, host } => {
-            gakun.use_profile(&profile, &host)?;
+        Commands::Add { profile, host, key, platform, user, port, hostname, rotate_after_days } => {
+            gakun.add(&profile, &host, &key, platform, user, port, hostname, rotate_after_days)?;
         }
-        Commands::Ls => {
-            gakun.list()?;
+        Commands::Use { profile, host } => match (profile, host) {
+            (Some(profile), Some(host)) => gakun.use_profile(&profile, &host)?,
+            (Some(profile), None) => gakun.pick_and_use(Some(&profile))?,
+            (None, _) => gakun.pick_and_use(None)?,
+        },
+        Commands::Ls { interactive } => {
+            if interactive {
+                gakun.browse()?;
+            } else {
+                gakun.list()?;
+            }
         }
         Commands::Detach => {
             gakun.detach()?;
         }
+        Commands::Edit { profile } => {
+            gakun.edit(&profile)?;
+        }
+        Commands::Rotate { profile, host } => {
+            gakun.rotate(&profile, &host)?;
+        }
+        Commands::Encrypt => {
+            gakun.encrypt()?;
+        }
+        Commands::Decrypt => {
+            gakun.decrypt()?;
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let plaintext = br#"{"profiles":{},"updated_at":0}"#;
+        let sealed = encrypt_blob(plaintext, "correct horse").unwrap();
+        assert!(sealed.starts_with(ENC_MAGIC));
+
+        let opened = decrypt_blob(&sealed, "correct horse").unwrap();
+        assert_eq!(opened, plaintext);
+    }
+
+    #[test]
+    fn decrypt_wrong_passphrase_fails() {
+        let sealed = encrypt_blob(b"secret", "right").unwrap();
+        assert!(decrypt_blob(&sealed, "wrong").is_err());
+    }
+
+    #[test]
+    fn decrypt_tampered_tag_fails() {
+        let mut sealed = encrypt_blob(b"secret", "pass").unwrap();
+        // Flip a bit in the trailing GCM tag; the auth check must reject it.
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert!(decrypt_blob(&sealed, "pass").is_err());
+    }
+
+    #[test]
+    fn decrypt_truncated_header_fails() {
+        assert!(decrypt_blob(ENC_MAGIC, "pass").is_err());
+    }
+
+    #[test]
+    fn fuzzy_match_requires_all_chars() {
+        assert!(fuzzy_match("work", "work/gitlab.com").is_some());
+        assert!(fuzzy_match("xyz", "work/gitlab.com").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_is_case_insensitive() {
+        assert!(fuzzy_match("WORK", "work/gitlab.com").is_some());
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_contiguous_and_boundary() {
+        let (contiguous, _) = fuzzy_match("git", "work/gitlab.com").unwrap();
+        let (scattered, _) = fuzzy_match("git", "gXiXt").unwrap();
+        assert!(contiguous > scattered);
+    }
+
+    #[test]
+    fn fuzzy_match_reports_matched_indices() {
+        let (_, matched) = fuzzy_match("wg", "work/gitlab.com").unwrap();
+        assert_eq!(matched, vec![0, 5]);
+    }
+}